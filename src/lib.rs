@@ -5,12 +5,34 @@
 
 use thiserror::Error;
 
+mod passwd;
+pub mod user_db;
+
+pub use passwd::Passwd;
+
 /// Error type for gecos errors. All public facing Results will carry this error type.
 #[derive(Error, Debug)]
 pub enum GecosError {
     /// Illegal character for passwd representations
     #[error("String contains invalid char, which is not allowed inside a Gecos field! (Chars ',', ':', '=', '\\', '\"', '\\n' are not allowed)")]
     IllegalPasswdChar(char),
+    /// A passwd line does not consist of exactly seven colon-separated fields
+    #[error("Passwd line does not consist of exactly seven colon-separated fields: `{0}`")]
+    IllegalPasswdLineFormat(String),
+    /// A numeric passwd field (uid/gid) could not be parsed as a `u32`
+    #[error("Passwd line contains an invalid numeric field `{field}`: `{value}`")]
+    IllegalPasswdNumericField {
+        /// the name of the offending field, e.g. "uid" or "gid"
+        field: &'static str,
+        /// the raw value that failed to parse
+        value: String,
+    },
+    /// Reading or writing a passwd database file failed
+    #[error("Failed to access passwd database file: {0}")]
+    Io(#[from] std::io::Error),
+    /// No user with the given username exists in the passwd database
+    #[error("No user named `{0}` found in passwd database")]
+    UserNotFound(String),
 }
 
 /// The raw Gecos struct.
@@ -37,6 +59,7 @@ pub enum GecosError {
 ///     work_phone: None,
 ///     home_phone: None,
 ///     other: vec![],
+///     comment: None,
 /// };
 ///
 /// // the most simple outcome, everything is just empty,
@@ -60,6 +83,7 @@ pub enum GecosError {
 ///         "Some info".to_string().try_into().unwrap(),
 ///         "More info".to_string().try_into().unwrap()
 ///     ],
+///     comment: None,
 /// };
 ///
 /// assert_eq!(gecos.to_gecos_string(), "Test Name,,,,Some info,More info")
@@ -79,7 +103,22 @@ pub enum GecosError {
 /// assert_eq!(gecos.full_name.unwrap().to_string(), "Some Person")
 /// ```
 ///
-#[derive(Clone, Debug)]
+/// Some systems store the GECOS field as a single free-text comment instead of the detailed,
+/// comma-separated layout above. If the input contains no comma at all, it is kept as a single
+/// [`Gecos::comment`] value instead of being forced into `full_name`, and `to_gecos_string` emits
+/// it back verbatim:
+///
+/// ```rust
+/// # use gecos::Gecos;
+/// #
+/// let gecos = Gecos::from_gecos_string("Local User Account").unwrap();
+///
+/// assert_eq!(gecos.to_gecos_string(), "Local User Account");
+/// assert!(gecos.full_name.is_none());
+/// assert_eq!(gecos.comment.unwrap().to_string(), "Local User Account");
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Gecos {
     /// like Guest, can be None if empty.
     pub full_name: Option<GecosSanitizedString>,
@@ -91,6 +130,10 @@ pub struct Gecos {
     pub home_phone: Option<GecosSanitizedString>,
     /// like a mail address or other important information, vector can be empty if there is no data.
     pub other: Vec<GecosSanitizedString>,
+    /// the "simple" form of the GECOS field: a single free-text comment instead of the detailed,
+    /// comma-separated layout above. Set by [Gecos::from_gecos_string] when the input contains no
+    /// comma at all, and re-emitted verbatim by [Gecos::to_gecos_string] when present.
+    pub comment: Option<GecosSanitizedString>,
 }
 
 /// A struct to ensure the string has none of [',', ':', '=', '\', '"', '\n'] in it, as this would break the gecos string object.
@@ -153,6 +196,47 @@ impl PartialEq for GecosSanitizedString {
     }
 }
 
+impl Eq for GecosSanitizedString {}
+
+impl std::hash::Hash for GecosSanitizedString {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.str.hash(state);
+    }
+}
+
+impl std::str::FromStr for GecosSanitizedString {
+    type Err = GecosError;
+
+    /// Parses a [GecosSanitizedString] from a string, wrapping [GecosSanitizedString::new].
+    ///
+    /// ```rust
+    /// # use gecos::GecosSanitizedString;
+    /// #
+    /// let sanitized: GecosSanitizedString = "Another name".parse().unwrap();
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s.to_string())
+    }
+}
+
+impl std::str::FromStr for Gecos {
+    type Err = GecosError;
+
+    /// Parses a [Gecos] from a string, wrapping [Gecos::from_gecos_string].
+    ///
+    /// ```rust
+    /// # use gecos::Gecos;
+    /// #
+    /// let gecos: Gecos = "a,b,,,".parse().unwrap();
+    ///
+    /// assert_eq!(gecos.full_name.unwrap().to_string(), "a");
+    /// assert_eq!(gecos.room.unwrap().to_string(), "b");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_gecos_string(s)
+    }
+}
+
 impl Gecos {
     /// Converts a [Gecos] object to a gecos string like in the passwd database.
     ///
@@ -166,11 +250,27 @@ impl Gecos {
     ///     work_phone: None,
     ///     home_phone: None,
     ///     other: vec![],
+    ///     comment: None,
     /// };
     ///
     /// assert_eq!(gecos.to_gecos_string(), "Test Name,,,,")
     /// ```
+    ///
+    /// If [`Gecos::comment`] is set, it is emitted verbatim instead, since it represents an
+    /// input which never had the detailed, comma-separated layout to begin with:
+    ///
+    /// ```rust
+    /// # use gecos::Gecos;
+    /// #
+    /// let gecos = Gecos::from_gecos_string("Local User Account").unwrap();
+    ///
+    /// assert_eq!(gecos.to_gecos_string(), "Local User Account")
+    /// ```
     pub fn to_gecos_string(&self) -> String {
+        if let Some(comment) = &self.comment {
+            return comment.to_string();
+        }
+
         macro_rules! gecos_element_to_string {
             ($sts:expr) => {
                 $sts.as_ref().unwrap_or(&"".to_string().try_into().unwrap())
@@ -220,20 +320,46 @@ impl Gecos {
     /// assert_eq!(gecos.other.iter().map(|val| val.to_string()).collect::<Vec<String>>(), ["Other"]);
     /// ```
     ///
-    /// or even incomplete
+    /// An input containing no comma at all is not forced into `full_name`. Instead, it is kept as
+    /// a single [`Gecos::comment`] value and round-tripped verbatim, since many systems store the
+    /// GECOS field as a single free-text comment rather than the detailed layout:
     ///
     /// ```rust
     /// # use gecos::{Gecos, GecosSanitizedString};
     /// #
     /// let gecos = Gecos::from_gecos_string("Some Person").unwrap();
     ///
-    /// assert_eq!(gecos.full_name.unwrap().to_string(), "Some Person");
+    /// assert_eq!(gecos.comment.unwrap().to_string(), "Some Person");
+    /// assert!(gecos.full_name.is_none());
     /// assert!(gecos.room.is_none());
     /// assert!(gecos.work_phone.is_none());
     /// assert!(gecos.home_phone.is_none());
     /// assert_eq!(gecos.other, Vec::<GecosSanitizedString>::new());
     /// ```
+    ///
+    /// This also applies to the empty string, which is one of the most common real-world GECOS
+    /// values (e.g. for `nobody` and many service accounts): it round-trips to itself instead of
+    /// being normalized to the detailed form's `,,,,`:
+    ///
+    /// ```rust
+    /// # use gecos::Gecos;
+    /// #
+    /// let gecos = Gecos::from_gecos_string("").unwrap();
+    ///
+    /// assert_eq!(gecos.to_gecos_string(), "");
+    /// ```
     pub fn from_gecos_string(input: &str) -> Result<Self, GecosError> {
+        if !input.contains(',') {
+            return Ok(Self {
+                full_name: None,
+                room: None,
+                work_phone: None,
+                home_phone: None,
+                other: vec![],
+                comment: Some(input.to_string().try_into()?),
+            });
+        }
+
         let mut splitted = input
             .split(',')
             .map(|val| -> Result<GecosSanitizedString, GecosError> { val.to_string().try_into() });
@@ -265,6 +391,7 @@ impl Gecos {
             work_phone: gecos_string_element_to_gecos_object_element!(splitted.next()),
             home_phone: gecos_string_element_to_gecos_object_element!(splitted.next()),
             other: splitted.collect::<Result<Vec<GecosSanitizedString>, GecosError>>()?,
+            comment: None,
         })
     }
 }
@@ -0,0 +1,132 @@
+use crate::{Gecos, GecosError};
+
+/// A full `/etc/passwd` entry, embedding the [Gecos] comment field.
+///
+/// See [the man page](https://man.freebsd.org/cgi/man.cgi?query=passwd&sektion=5) for an introduction of the format.
+///
+/// ## Parse a passwd entry
+///
+/// ```rust
+/// # use gecos::Passwd;
+/// #
+/// let passwd = Passwd::from_passwd_line("testuser:x:1001:1001:full Name,004,00342,001-2312,mail@x.com:/home/test:/bin/test").unwrap();
+///
+/// assert_eq!(passwd.username, "testuser");
+/// assert_eq!(passwd.uid, 1001);
+/// assert_eq!(passwd.gid, 1001);
+/// assert_eq!(passwd.gecos.full_name.unwrap().to_string(), "full Name");
+/// assert_eq!(passwd.home_dir, "/home/test");
+/// assert_eq!(passwd.shell, "/bin/test");
+/// ```
+///
+/// ## Convert back to a passwd line
+///
+/// ```rust
+/// # use gecos::Passwd;
+/// #
+/// let line = "testuser:x:1001:1001:full Name,004,00342,001-2312,mail@x.com:/home/test:/bin/test";
+/// let passwd = Passwd::from_passwd_line(line).unwrap();
+///
+/// assert_eq!(passwd.to_passwd_line(), line);
+/// ```
+///
+/// An empty GECOS field, common for system accounts like `nobody`, round-trips to itself rather
+/// than being normalized to the detailed form's `,,,,`:
+///
+/// ```rust
+/// # use gecos::Passwd;
+/// #
+/// let line = "nobody:x:65534:65534::/nonexistent:/usr/sbin/nologin";
+///
+/// assert_eq!(Passwd::from_passwd_line(line).unwrap().to_passwd_line(), line);
+/// ```
+///
+/// Note that this round-trip is only byte-for-byte when the GECOS field was already in its
+/// canonical five-field form, empty, or (for the [simple single-comment form](crate::Gecos::comment))
+/// verbatim. A detailed GECOS string with fewer than five fields but at least one comma is
+/// normalized by [crate::Gecos::to_gecos_string] to the canonical form, padding the missing
+/// trailing fields:
+///
+/// ```rust
+/// # use gecos::Passwd;
+/// #
+/// let passwd = Passwd::from_passwd_line("testuser:x:1001:1001:Full Name,Room:/home/test:/bin/test").unwrap();
+///
+/// assert_eq!(
+///     passwd.to_passwd_line(),
+///     "testuser:x:1001:1001:Full Name,Room,,,:/home/test:/bin/test"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Passwd {
+    /// the login name
+    pub username: String,
+    /// the (usually shadowed) password field, commonly just `x`
+    pub password: String,
+    /// the numeric user id
+    pub uid: u32,
+    /// the numeric group id
+    pub gid: u32,
+    /// the GECOS comment field
+    pub gecos: Gecos,
+    /// the path to the user's home directory
+    pub home_dir: String,
+    /// the path to the user's login shell
+    pub shell: String,
+}
+
+impl Passwd {
+    /// Parses a single `/etc/passwd` line into a [Passwd] object.
+    ///
+    /// The line must consist of exactly seven colon-separated fields. The fifth field is
+    /// delegated to [Gecos::from_gecos_string].
+    pub fn from_passwd_line(line: &str) -> Result<Self, GecosError> {
+        let fields: Vec<&str> = line.split(':').collect();
+
+        let [username, password, uid, gid, gecos, home_dir, shell]: [&str; 7] = fields
+            .try_into()
+            .map_err(|_| GecosError::IllegalPasswdLineFormat(line.to_string()))?;
+
+        let uid = uid
+            .parse::<u32>()
+            .map_err(|_| GecosError::IllegalPasswdNumericField {
+                field: "uid",
+                value: uid.to_string(),
+            })?;
+        let gid = gid
+            .parse::<u32>()
+            .map_err(|_| GecosError::IllegalPasswdNumericField {
+                field: "gid",
+                value: gid.to_string(),
+            })?;
+
+        Ok(Self {
+            username: username.to_string(),
+            password: password.to_string(),
+            uid,
+            gid,
+            gecos: Gecos::from_gecos_string(gecos)?,
+            home_dir: home_dir.to_string(),
+            shell: shell.to_string(),
+        })
+    }
+
+    /// Converts this [Passwd] object back to a single `/etc/passwd` line.
+    ///
+    /// The GECOS field is re-emitted via [Gecos::to_gecos_string]: a simple single-comment (or
+    /// empty) field is emitted verbatim, a detailed one is normalized to the canonical five-field
+    /// form, so it only round-trips byte-for-byte if it was already in that form (see the [Passwd]
+    /// docs for an example of the normalization).
+    pub fn to_passwd_line(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.username,
+            self.password,
+            self.uid,
+            self.gid,
+            self.gecos.to_gecos_string(),
+            self.home_dir,
+            self.shell,
+        )
+    }
+}
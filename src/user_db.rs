@@ -0,0 +1,329 @@
+//! Read access to `/etc/passwd`-style user databases, plus an in-place GECOS rewrite.
+//!
+//! This module turns the crate from a pure string codec into something that can actually query
+//! the local user database. The [UserDBRead] trait is the common interface; [PasswdFile] is the
+//! implementation backed by an on-disk passwd file. [update_gecos] rewrites the GECOS field of a
+//! single user in such a file.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::{Gecos, GecosError, Passwd};
+
+/// Common read interface for a user database, so alternative backends (an in-memory `Vec`, a
+/// test fixture file, ...) can implement the same interface as [PasswdFile].
+pub trait UserDBRead {
+    /// Returns all users contained in the database.
+    ///
+    /// Blank, `#`-comment, and NIS compat lines are skipped, but any other line that is not a
+    /// well-formed seven-field passwd entry (wrong field count, non-numeric uid/gid, illegal GECOS
+    /// character) fails the entire call, unlike `getpwent(3)` which skips unparseable entries.
+    fn get_all_users(&self) -> Result<Vec<Passwd>, GecosError>;
+
+    /// Returns the user with the given username, if present.
+    fn get_user_by_name(&self, username: &str) -> Result<Option<Passwd>, GecosError>;
+
+    /// Returns the user with the given numeric uid, if present.
+    fn get_user_by_id(&self, uid: u32) -> Result<Option<Passwd>, GecosError>;
+}
+
+/// A user database backed by a passwd file on disk, defaulting to `/etc/passwd`.
+///
+/// ```rust,no_run
+/// # use gecos::user_db::{PasswdFile, UserDBRead};
+/// #
+/// let db = PasswdFile::default();
+/// let all_users = db.get_all_users().unwrap();
+/// let root = db.get_user_by_id(0).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct PasswdFile {
+    path: PathBuf,
+}
+
+impl PasswdFile {
+    /// Creates a [PasswdFile] reading from the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Default for PasswdFile {
+    /// Creates a [PasswdFile] reading from the system default `/etc/passwd`.
+    fn default() -> Self {
+        Self::new("/etc/passwd")
+    }
+}
+
+impl UserDBRead for PasswdFile {
+    fn get_all_users(&self) -> Result<Vec<Passwd>, GecosError> {
+        fs::read_to_string(&self.path)?
+            .lines()
+            .filter(|line| is_user_entry_line(line))
+            .map(Passwd::from_passwd_line)
+            .collect()
+    }
+
+    fn get_user_by_name(&self, username: &str) -> Result<Option<Passwd>, GecosError> {
+        Ok(self
+            .get_all_users()?
+            .into_iter()
+            .find(|user| user.username == username))
+    }
+
+    fn get_user_by_id(&self, uid: u32) -> Result<Option<Passwd>, GecosError> {
+        Ok(self.get_all_users()?.into_iter().find(|user| user.uid == uid))
+    }
+}
+
+/// Returns `false` for lines that are not actual user entries: blank lines, `#` comments, and NIS
+/// compat entries (`+name`, `+@group`, `-name`, ...), none of which are seven-field passwd lines.
+fn is_user_entry_line(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with('#') && !line.starts_with('+') && !line.starts_with('-')
+}
+
+/// Updates only the GECOS field of a single user in a passwd file, mirroring what `chfn` does.
+///
+/// All other fields and lines are left untouched. The file is replaced atomically: the new
+/// content is written to a temporary file in the same directory, the original file's permissions
+/// and ownership are copied onto it, it is `fsync`ed and renamed over the original path, and
+/// finally the containing directory is `fsync`ed so the rename itself is durable. The temporary
+/// file is removed again if any step before the rename fails.
+///
+/// Returns [GecosError::UserNotFound] if no line for `username` exists in the file.
+///
+/// ```rust,no_run
+/// # use gecos::Gecos;
+/// # use gecos::user_db::update_gecos;
+/// #
+/// let new_gecos = Gecos::from_gecos_string("New Full Name,,,,").unwrap();
+/// update_gecos("/etc/passwd", "testuser", &new_gecos).unwrap();
+/// ```
+pub fn update_gecos(path: impl AsRef<Path>, username: &str, new: &Gecos) -> Result<(), GecosError> {
+    let path = path.as_ref();
+    let original = fs::read_to_string(path)?;
+    let original_metadata = fs::metadata(path)?;
+    let mut found = false;
+
+    let updated_lines: Vec<String> = original
+        .lines()
+        .map(|line| {
+            let fields: Vec<&str> = line.split(':').collect();
+            if fields.len() == 7 && fields[0] == username {
+                found = true;
+                format!(
+                    "{}:{}:{}:{}:{}:{}:{}",
+                    fields[0],
+                    fields[1],
+                    fields[2],
+                    fields[3],
+                    new.to_gecos_string(),
+                    fields[5],
+                    fields[6],
+                )
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    if !found {
+        return Err(GecosError::UserNotFound(username.to_string()));
+    }
+
+    let mut content = updated_lines.join("\n");
+    if original.ends_with('\n') {
+        content.push('\n');
+    }
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("passwd");
+    let tmp_path = dir.join(format!(".{file_name}.{}.tmp", std::process::id()));
+
+    if let Err(err) = write_temp_file(&tmp_path, &content, &original_metadata) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err.into());
+    }
+
+    if let Err(err) = fs::rename(&tmp_path, path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err.into());
+    }
+
+    sync_dir(dir)?;
+
+    Ok(())
+}
+
+/// Writes `content` to `tmp_path`, copying `source_metadata`'s permissions (and, on Unix,
+/// ownership) onto it, and `fsync`s it before returning.
+fn write_temp_file(
+    tmp_path: &Path,
+    content: &str,
+    source_metadata: &fs::Metadata,
+) -> std::io::Result<()> {
+    let mut tmp_file = fs::File::create(tmp_path)?;
+    tmp_file.set_permissions(source_metadata.permissions())?;
+    preserve_ownership(tmp_path, source_metadata)?;
+    tmp_file.write_all(content.as_bytes())?;
+    tmp_file.sync_all()?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn preserve_ownership(tmp_path: &Path, source_metadata: &fs::Metadata) -> std::io::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::os::unix::fs::chown(
+        tmp_path,
+        Some(source_metadata.uid()),
+        Some(source_metadata.gid()),
+    )
+}
+
+#[cfg(not(unix))]
+fn preserve_ownership(_tmp_path: &Path, _source_metadata: &fs::Metadata) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// `fsync`s a directory so that prior renames into it are durable. A no-op on platforms where
+/// directories cannot be opened as files.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> Result<(), GecosError> {
+    fs::File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> Result<(), GecosError> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// A trivial in-memory [UserDBRead] backend, demonstrating that the trait is not tied to
+    /// [PasswdFile].
+    struct InMemoryUserDb(Vec<Passwd>);
+
+    impl UserDBRead for InMemoryUserDb {
+        fn get_all_users(&self) -> Result<Vec<Passwd>, GecosError> {
+            Ok(self.0.clone())
+        }
+
+        fn get_user_by_name(&self, username: &str) -> Result<Option<Passwd>, GecosError> {
+            Ok(self.0.iter().find(|user| user.username == username).cloned())
+        }
+
+        fn get_user_by_id(&self, uid: u32) -> Result<Option<Passwd>, GecosError> {
+            Ok(self.0.iter().find(|user| user.uid == uid).cloned())
+        }
+    }
+
+    /// Returns a path to a not-yet-existing file in the system temp directory, unique to this
+    /// test process and call.
+    fn temp_file_path(name: &str) -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!(
+            "gecos-user_db-test-{}-{}-{name}",
+            std::process::id(),
+            unique
+        ))
+    }
+
+    #[test]
+    fn get_all_users_skips_non_entry_lines() {
+        let path = temp_file_path("passwd");
+        fs::write(
+            &path,
+            "# a comment\n\
+             \n\
+             +@netgroup\n\
+             -baduser::::::\n\
+             testuser:x:1001:1001:Full Name,,,:/home/testuser:/bin/sh\n",
+        )
+        .unwrap();
+
+        let db = PasswdFile::new(&path);
+        let users = db.get_all_users().unwrap();
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].username, "testuser");
+    }
+
+    #[test]
+    fn in_memory_backend_implements_user_db_read() {
+        let db = InMemoryUserDb(vec![Passwd::from_passwd_line(
+            "testuser:x:1001:1001:Full Name,,,:/home/testuser:/bin/sh",
+        )
+        .unwrap()]);
+
+        assert_eq!(db.get_all_users().unwrap().len(), 1);
+        assert!(db.get_user_by_name("testuser").unwrap().is_some());
+        assert!(db.get_user_by_name("nobody").unwrap().is_none());
+        assert!(db.get_user_by_id(1001).unwrap().is_some());
+        assert!(db.get_user_by_id(9999).unwrap().is_none());
+    }
+
+    #[test]
+    fn update_gecos_changes_only_the_matched_line() {
+        let path = temp_file_path("passwd");
+        let original = "# a comment\n\
+             \n\
+             alice:x:1000:1000:Alice,,,:/home/alice:/bin/sh\n\
+             bob:x:1001:1001:Bob,,,:/home/bob:/bin/sh\n";
+        fs::write(&path, original).unwrap();
+
+        let new_gecos = Gecos::from_gecos_string("Bobby,,,,").unwrap();
+        update_gecos(&path, "bob", &new_gecos).unwrap();
+
+        let updated = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            updated,
+            "# a comment\n\
+             \n\
+             alice:x:1000:1000:Alice,,,:/home/alice:/bin/sh\n\
+             bob:x:1001:1001:Bobby,,,,:/home/bob:/bin/sh\n"
+        );
+    }
+
+    #[test]
+    fn update_gecos_preserves_trailing_newline_presence() {
+        let path = temp_file_path("passwd");
+        fs::write(&path, "bob:x:1001:1001:Bob,,,:/home/bob:/bin/sh").unwrap();
+
+        let new_gecos = Gecos::from_gecos_string("Bobby,,,,").unwrap();
+        update_gecos(&path, "bob", &new_gecos).unwrap();
+
+        let updated = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(!updated.ends_with('\n'));
+        assert_eq!(updated, "bob:x:1001:1001:Bobby,,,,:/home/bob:/bin/sh");
+    }
+
+    #[test]
+    fn update_gecos_returns_user_not_found() {
+        let path = temp_file_path("passwd");
+        fs::write(&path, "bob:x:1001:1001:Bob,,,:/home/bob:/bin/sh\n").unwrap();
+
+        let new_gecos = Gecos::from_gecos_string("Nope,,,,").unwrap();
+        let result = update_gecos(&path, "nobody", &new_gecos);
+
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(GecosError::UserNotFound(username)) if username == "nobody"));
+    }
+}